@@ -1,14 +1,53 @@
+use crate::error::{PolarsError, PolarsResult};
 use crate::prelude::PlHashMap;
+use hashbrown::hash_map::RawEntryMut;
 use once_cell::sync::Lazy;
 use smartstring::{LazyCompact, SmartString};
 use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// The single hash function every insert and lookup against `SCacheInner::map` must go through.
+///
+/// `StrHashGlobal`'s `Hash`/`Eq` key on this precomputed value rather than re-hashing the string,
+/// and all accesses go through `raw_entry`/`raw_entry_mut` with this hash so two different hash
+/// functions for the same string can never land it in two different slots (which would otherwise
+/// silently assign one string two different categorical ids).
+pub(crate) fn str_hash(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Look up `s` (already hashed with [`str_hash`]) without taking a write lock or touching the
+/// map's own `BuildHasher`.
+fn raw_get(map: &PlHashMap<StrHashGlobal, u32>, s: &str, hash: u64) -> Option<u32> {
+    map.raw_entry()
+        .from_hash(hash, |key| key.hash == hash && key.str == s)
+        .map(|(_, id)| *id)
+}
+
 pub(crate) static USE_STRING_CACHE: AtomicBool = AtomicBool::new(false);
 
+/// Approximate per-entry bookkeeping overhead charged against the string cache's byte limit, on
+/// top of the string's own bytes (hash table slot, the `u32` id, the `StrHashGlobal` wrapper).
+const ENTRY_OVERHEAD_BYTES: usize =
+    std::mem::size_of::<StrHashGlobal>() + std::mem::size_of::<u32>();
+
+/// Number of categories currently held by the live string cache generation.
+static CACHE_ENTRIES: AtomicUsize = AtomicUsize::new(0);
+/// Total bytes (string data + [`ENTRY_OVERHEAD_BYTES`] per entry) held by the live generation.
+///
+/// Tracked in an atomic, separate from the `RwLock<SCacheInner>`, so [`string_cache_size`] can be
+/// read without taking the cache lock.
+static CACHE_BYTES: AtomicUsize = AtomicUsize::new(0);
+/// Soft limit on [`CACHE_BYTES`]. `usize::MAX` (the default) means unbounded.
+static CACHE_LIMIT_BYTES: AtomicUsize = AtomicUsize::new(usize::MAX);
+
 pub fn with_string_cache<F: FnOnce() -> T, T>(func: F) -> T {
     toggle_string_cache(true);
     let out = func();
@@ -38,9 +77,86 @@ pub(crate) fn use_string_cache() -> bool {
     USE_STRING_CACHE.load(Ordering::Acquire)
 }
 
+/// Current `(entry count, byte count)` held by the global string cache's live generation.
+pub fn string_cache_size() -> (usize, usize) {
+    (
+        CACHE_ENTRIES.load(Ordering::Acquire),
+        CACHE_BYTES.load(Ordering::Acquire),
+    )
+}
+
+/// The generation a [`CategoricalChunked`](crate::datatypes::CategoricalChunked) was built
+/// against is still the live one iff this returns `true`. Callers decoding a categorical's ids
+/// back into strings must check this first: once the cache has moved to a new generation (via
+/// [`reset_string_cache`] or a [`set_string_cache_limit`] eviction), the old ids may have been
+/// reassigned to different strings and must not be looked up against the current map.
+pub(crate) fn is_current_generation(uuid: u128) -> bool {
+    STRING_CACHE.read_map().uuid == uuid
+}
+
+/// Cap the global string cache at `max_bytes`, counted as each category's string bytes plus
+/// [`ENTRY_OVERHEAD_BYTES`] per entry (pass `usize::MAX` to disable the limit again).
+///
+/// Because a live [`CategoricalChunked`](crate::datatypes::CategoricalChunked)'s ids must stay
+/// valid for as long as it exists, hitting the limit cannot simply drop individual entries.
+/// Instead it evicts the whole generation: `uuid` is bumped and the map starts empty again, which
+/// is also what [`reset_string_cache`] does. Any categorical still carrying ids from the
+/// superseded generation must be treated as stale by its readers rather than decoded against the
+/// new map, since the same id may now mean a different string — see [`is_current_generation`].
+///
+/// A single category that does not fit under `max_bytes` even in an empty generation can never be
+/// satisfied by evicting, so inserting it returns a [`ComputeError`](PolarsError::ComputeError)
+/// instead of evicting the generation on every insert of it.
+pub fn set_string_cache_limit(max_bytes: usize) {
+    CACHE_LIMIT_BYTES.store(max_bytes, Ordering::Release);
+}
+
+/// A portable snapshot of the global string cache's category id assignments.
+///
+/// Produced by [`export_string_cache`] and consumed by [`import_string_cache`] to give a worker
+/// process the exact same `(category, id)` assignments a producer used, so categoricals built
+/// against the two caches compare and join correctly once merged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoryDictionary {
+    /// `(id, category)` pairs, in id order.
+    pub categories: Vec<(u32, String)>,
+    /// The generation of the cache this snapshot was taken from.
+    pub uuid: u128,
+}
+
+/// Snapshot the global string cache into a portable artifact that can be sent to another
+/// process and merged back in with [`import_string_cache`].
+pub fn export_string_cache() -> CategoryDictionary {
+    STRING_CACHE.export()
+}
+
+/// Merge a [`CategoryDictionary`] exported from another process into the global string cache.
+///
+/// Existing ids are preserved where the category already matches. If `dict` assigns a different
+/// category to an id that is already in use locally, this returns an error instead of silently
+/// remapping either side's ids. If the local cache was empty (the common case: a fresh worker
+/// importing a producer's dictionary before building anything of its own), its generation marker
+/// is set to `dict.uuid`, so categoricals rebuilt from `dict` and ones built locally afterwards
+/// are recognized as belonging to the same generation. If the local cache already had categories
+/// of its own generation, that generation marker is left alone instead, since live categoricals
+/// built against it remain valid and must not be treated as stale by this merge.
+pub fn import_string_cache(dict: &CategoryDictionary) -> PolarsResult<()> {
+    STRING_CACHE.import(dict)
+}
+
 pub(crate) struct SCacheInner {
     pub(crate) map: PlHashMap<StrHashGlobal, u32>,
     pub(crate) uuid: u128,
+    /// Backing storage for [`InternedStr`] handles, keyed by the same id as `map` so every insert
+    /// path (`get_cat`, `intern`, `import`) only has to add one entry here per new id, regardless
+    /// of whether ids are dense (the common case) or, after an import, sparse. `Arc` rather than
+    /// a directly-owned `Box<str>` so a live `InternedStr` keeps its string alive even if this
+    /// generation is later evicted or reset out from under it.
+    interned: PlHashMap<u32, Arc<str>>,
+    /// Next id [`StringCache::insert_new`] will hand out. Tracked explicitly rather than derived
+    /// from `map.len()` so that ids adopted verbatim from an [`import_string_cache`] (which need
+    /// not be dense, or start at 0) can't later be handed out again to an unrelated string.
+    next_id: u32,
 }
 
 impl Default for SCacheInner {
@@ -51,6 +167,8 @@ impl Default for SCacheInner {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_nanos(),
+            interned: Default::default(),
+            next_id: 0,
         }
     }
 }
@@ -59,22 +177,340 @@ impl Default for SCacheInner {
 /// In *eager* you need to specifically toggle global string cache to have a global effect.
 /// In *lazy* it is toggled on at the start of a computation run and turned of (deleted) when a
 /// result is produced.
-pub(crate) struct StringCache(pub(crate) Mutex<SCacheInner>);
+///
+/// Guarded by an `RwLock` rather than a `Mutex`: once a set of categories is warm, almost all
+/// traffic is lookups of already-interned strings, and those only need the shared read guard.
+/// Only inserting a category not seen before (or resetting the cache) takes the exclusive write
+/// guard, so unrelated readers never block each other on the steady-state path.
+pub(crate) struct StringCache(pub(crate) RwLock<SCacheInner>);
 
 impl StringCache {
-    pub(crate) fn lock_map(&self) -> MutexGuard<SCacheInner> {
-        self.0.lock().unwrap()
+    /// Shared guard for lookups. Multiple threads can hold this concurrently. `pub(crate)` so
+    /// callers outside this module that only need to read/iterate the map (for example a
+    /// categorical builder checking whether a category is already known) can get the shared-read
+    /// fast path this type exists to provide, rather than reaching for [`lock_map`](Self::lock_map).
+    pub(crate) fn read_map(&self) -> RwLockReadGuard<SCacheInner> {
+        self.0.read().unwrap()
+    }
+
+    /// Exclusive guard for inserts and resets. Kept under the old name since it is also used as
+    /// the "I need to mutate" entry point throughout this module, but note that it now takes the
+    /// **write** half of the `RwLock` — a call site that only iterates or reads `map`/`interned`
+    /// must use [`read_map`](Self::read_map) instead, or it silently forces every such access
+    /// onto the exclusive path this type exists to avoid.
+    pub(crate) fn lock_map(&self) -> RwLockWriteGuard<SCacheInner> {
+        self.0.write().unwrap()
     }
 
     pub(crate) fn clear(&self) {
         let mut lock = self.lock_map();
         *lock = Default::default();
+        CACHE_ENTRIES.store(0, Ordering::Release);
+        CACHE_BYTES.store(0, Ordering::Release);
+    }
+
+    /// Insert a new, not-yet-present category into `lock` and update the byte/entry accounting,
+    /// evicting the whole generation first (bumping `uuid`, starting a fresh map) if doing so
+    /// would make room for it under [`CACHE_LIMIT_BYTES`]. Returns the id assigned to `s`.
+    ///
+    /// Errors instead of evicting if `s` alone can never fit under the configured limit — without
+    /// this, a limit smaller than one entry's size would otherwise evict the generation on every
+    /// single insert of it, thrashing the cache down to at most one live entry forever.
+    ///
+    /// Callers must already have checked `s` is absent from `lock.map`.
+    fn insert_new(&self, lock: &mut SCacheInner, s: &str, hash: u64) -> PolarsResult<u32> {
+        let added_bytes = s.len() + ENTRY_OVERHEAD_BYTES;
+        let limit = CACHE_LIMIT_BYTES.load(Ordering::Acquire);
+
+        if added_bytes > limit {
+            return Err(PolarsError::ComputeError(
+                format!(
+                    "string cache limit exceeded: category of {added_bytes} bytes does not fit \
+                     under the configured limit of {limit} bytes"
+                )
+                .into(),
+            ));
+        }
+
+        if CACHE_BYTES.load(Ordering::Acquire) + added_bytes > limit {
+            *lock = Default::default();
+            CACHE_ENTRIES.store(0, Ordering::Release);
+            CACHE_BYTES.store(0, Ordering::Release);
+        }
+
+        let id = lock.next_id;
+        lock.next_id += 1;
+        match lock
+            .map
+            .raw_entry_mut()
+            .from_hash(hash, |key| key.hash == hash && key.str == s)
+        {
+            // `s` was just evicted or confirmed absent by the caller.
+            RawEntryMut::Occupied(_) => unreachable!(),
+            RawEntryMut::Vacant(entry) => {
+                entry.insert_with_hasher(hash, StrHashGlobal::new(s.into(), hash), id, |key| {
+                    key.hash
+                });
+            },
+        }
+        lock.interned.insert(id, Arc::from(s));
+        CACHE_ENTRIES.fetch_add(1, Ordering::AcqRel);
+        CACHE_BYTES.fetch_add(added_bytes, Ordering::AcqRel);
+        Ok(id)
+    }
+
+    /// Get the categorical id belonging to `s`, inserting it into the global map if it is not
+    /// yet present.
+    ///
+    /// Every call takes the shared [`read_map`](Self::read_map) guard, both to check that the
+    /// calling thread's local front cache still belongs to the live generation and, on a local
+    /// miss, to look `s` up in the global map. Only when `s` is absent from the global map does it
+    /// take the exclusive [`lock_map`](Self::lock_map) guard to insert it. The local cache is filled
+    /// after either kind of miss. Lookups are not lock-free: a local hit still takes the read guard.
+    ///
+    /// Errors only if [`set_string_cache_limit`] has been set below `s`'s own size; see
+    /// [`insert_new`](Self::insert_new).
+    pub(crate) fn get_cat(&self, s: &str) -> PolarsResult<u32> {
+        let hash = str_hash(s);
+
+        LOCAL_CACHE.with(|cell| {
+            let mut local = cell.borrow_mut();
+
+            // Already-interned strings are the common case once the cache is warm, so try the
+            // shared read guard first: concurrent readers on other threads don't block us here.
+            // The thread-local cache is only valid for the generation of the global cache it was
+            // built against, so the uuid check is folded into this same guard acquisition rather
+            // than taking a separate read lock just for it.
+            {
+                let global = self.read_map();
+                if local.uuid != global.uuid {
+                    local.map.clear();
+                    local.uuid = global.uuid;
+                } else if let Some(id) = local.map.get(s) {
+                    return Ok(*id);
+                }
+
+                if let Some(id) = raw_get(&global.map, s, hash) {
+                    drop(global);
+                    local.map.insert(s.into(), id);
+                    return Ok(id);
+                }
+            }
+
+            // Miss: take the exclusive write guard and re-check, since another thread may have
+            // inserted `s` (or reset the cache) between dropping the read guard and getting here.
+            let mut global = self.lock_map();
+            if global.uuid != local.uuid {
+                local.map.clear();
+                local.uuid = global.uuid;
+            }
+            let id = match raw_get(&global.map, s, hash) {
+                Some(id) => id,
+                None => self.insert_new(&mut global, s, hash)?,
+            };
+            drop(global);
+
+            local.map.insert(s.into(), id);
+            Ok(id)
+        })
+    }
+
+    /// Intern `s`, returning a handle that shares its allocation with any other interning of an
+    /// equal string in the same cache generation. Like [`get_cat`](Self::get_cat), this does not
+    /// check whether the string cache is toggled on: it is a standalone interning facility layered
+    /// on the same global map.
+    ///
+    /// Errors only if [`set_string_cache_limit`] has been set below `s`'s own size; see
+    /// [`insert_new`](Self::insert_new).
+    pub(crate) fn intern(&self, s: &str) -> PolarsResult<InternedStr> {
+        let hash = str_hash(s);
+
+        // Already-interned strings are the common case, so try the shared read guard first.
+        {
+            let global = self.read_map();
+            if let Some(id) = raw_get(&global.map, s, hash) {
+                let data = global.interned[&id].clone();
+                return Ok(InternedStr { data, hash });
+            }
+        }
+
+        let mut lock = self.lock_map();
+        let id = match raw_get(&lock.map, s, hash) {
+            Some(id) => id,
+            None => self.insert_new(&mut lock, s, hash)?,
+        };
+        let data = lock.interned[&id].clone();
+
+        Ok(InternedStr { data, hash })
+    }
+
+    /// See [`export_string_cache`].
+    fn export(&self) -> CategoryDictionary {
+        let lock = self.read_map();
+        let mut categories: Vec<(u32, String)> = lock
+            .map
+            .iter()
+            .map(|(s, id)| (*id, s.str.as_str().to_string()))
+            .collect();
+        categories.sort_unstable_by_key(|(id, _)| *id);
+
+        CategoryDictionary {
+            categories,
+            uuid: lock.uuid,
+        }
+    }
+
+    /// See [`import_string_cache`].
+    fn import(&self, dict: &CategoryDictionary) -> PolarsResult<()> {
+        let mut lock = self.lock_map();
+        let was_empty = lock.map.is_empty();
+
+        let local_by_id: PlHashMap<u32, &str> = lock
+            .map
+            .iter()
+            .map(|(s, id)| (*id, s.str.as_str()))
+            .collect();
+
+        // Reject the merge outright if any id or category would have to be silently remapped,
+        // rather than guessing which side is right.
+        for (id, category) in &dict.categories {
+            let hash = str_hash(category);
+            if let Some(local_category) = local_by_id.get(id) {
+                if *local_category != category {
+                    return Err(PolarsError::ComputeError(
+                        format!(
+                            "cannot import string cache: id {id} is assigned to '{local_category}' \
+                             locally, but the imported dictionary assigns it to '{category}'"
+                        )
+                        .into(),
+                    ));
+                }
+            } else if let Some(local_id) = raw_get(&lock.map, category, hash) {
+                if local_id != *id {
+                    return Err(PolarsError::ComputeError(
+                        format!(
+                            "cannot import string cache: category '{category}' is assigned id \
+                             {local_id} locally, but the imported dictionary assigns it id {id}"
+                        )
+                        .into(),
+                    ));
+                }
+            }
+        }
+
+        for (id, category) in &dict.categories {
+            let hash = str_hash(category);
+            if raw_get(&lock.map, category, hash).is_some() {
+                continue;
+            }
+            match lock
+                .map
+                .raw_entry_mut()
+                .from_hash(hash, |key| key.hash == hash && key.str == category.as_str())
+            {
+                RawEntryMut::Occupied(_) => unreachable!(),
+                RawEntryMut::Vacant(entry) => {
+                    entry.insert_with_hasher(
+                        hash,
+                        StrHashGlobal::new(category.as_str().into(), hash),
+                        *id,
+                        |key| key.hash,
+                    );
+                },
+            }
+            lock.interned.insert(*id, Arc::from(category.as_str()));
+            // Ids adopted verbatim from the dictionary must never be handed out again by
+            // `insert_new`, whether or not they happen to be dense.
+            lock.next_id = lock.next_id.max(*id + 1);
+            CACHE_ENTRIES.fetch_add(1, Ordering::AcqRel);
+            CACHE_BYTES.fetch_add(category.len() + ENTRY_OVERHEAD_BYTES, Ordering::AcqRel);
+        }
+
+        // Only adopt the producer's generation marker when the local cache was empty before this
+        // merge (the common "fresh worker" case). If it already held categories of its own
+        // generation, every live `CategoricalChunked` built against that generation's uuid is
+        // still valid — even though this merge didn't conflict with any of them — so overwriting
+        // `uuid` here would make `is_current_generation` wrongly treat them as stale.
+        if was_empty {
+            lock.uuid = dict.uuid;
+        }
+
+        Ok(())
+    }
+}
+
+/// A lightweight interned-string handle backed by the global [`STRING_CACHE`].
+///
+/// Within one string cache generation, two interns of an equal string always share the same
+/// allocation, so [`PartialEq`] can usually skip the string bytes entirely via an `Arc` pointer
+/// compare. But [`reset_string_cache`] and size-limit eviction mint a *new* allocation for the
+/// same string in the next generation, so two handles from different generations can be equal
+/// strings yet fail the pointer compare; since [`Hash`] always hashes equal for them (it only
+/// writes the precomputed, allocation-independent `hash`), `eq` falls back to a byte compare on a
+/// pointer mismatch rather than risk `Hash`/`Eq` disagreeing — which would silently double-count
+/// one string as two in a `HashSet`/`HashMap` key position (exactly the join/group-by/unique paths
+/// this type exists for). [`Deref`](std::ops::Deref) gives cheap read access to the underlying
+/// `str` when needed. The allocation is reference-counted rather than tied to the generation it
+/// was interned against, so a live `InternedStr` keeps its string alive even across a generation
+/// that replaces it.
+#[derive(Clone, Eq, Debug)]
+pub struct InternedStr {
+    data: Arc<str>,
+    hash: u64,
+}
+
+impl PartialEq for InternedStr {
+    fn eq(&self, other: &Self) -> bool {
+        // Pointer equality is the fast path within one generation; two equal strings minted in
+        // different generations don't share an allocation, so fall back to a byte compare (cheap
+        // to reach for, since disagreeing hashes would already have short-circuited first).
+        Arc::ptr_eq(&self.data, &other.data)
+            || (self.hash == other.hash && *self.data == *other.data)
     }
 }
 
+impl Hash for InternedStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash)
+    }
+}
+
+impl std::ops::Deref for InternedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.data
+    }
+}
+
+/// Intern `s` against the global string cache, returning a handle that is hashed without touching
+/// the string bytes and, within one cache generation, compared by pointer.
+///
+/// Interning the same string twice within one cache generation returns a handle pointing at the
+/// same allocation; after a reset or eviction the same string gets a new allocation.
+/// Errors only if [`set_string_cache_limit`] has been set below `s`'s own size.
+pub(crate) fn intern(s: &str) -> PolarsResult<InternedStr> {
+    STRING_CACHE.intern(s)
+}
+
+/// Per-thread front cache layered over the global [`StringCache`]. Filled on every lookup that
+/// misses it, whether the string was found in the global map or newly inserted there. Consulting
+/// it still takes the global cache's shared read guard, to compare `uuid` first.
+#[derive(Default)]
+struct LocalCache {
+    map: PlHashMap<SmartString<LazyCompact>, u32>,
+    /// The `uuid` of the global [`SCacheInner`] this local cache was populated from. A mismatch
+    /// means the global cache was reset and this local cache must be dropped.
+    uuid: u128,
+}
+
+thread_local! {
+    static LOCAL_CACHE: RefCell<LocalCache> = RefCell::new(LocalCache::default());
+}
+
 impl Default for StringCache {
     fn default() -> Self {
-        StringCache(Mutex::new(Default::default()))
+        StringCache(RwLock::new(Default::default()))
     }
 }
 
@@ -111,3 +547,176 @@ impl Borrow<str> for StrHashGlobal {
         self.str.as_str()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    // The string cache is process-global, so tests that touch it must not run concurrently with
+    // each other.
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn get_cat_dedups_and_invalidates_local_cache_on_reset() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        let id_a = STRING_CACHE.get_cat("foo").unwrap();
+        let id_b = STRING_CACHE.get_cat("foo").unwrap();
+        assert_eq!(id_a, id_b);
+
+        let id_bar = STRING_CACHE.get_cat("bar").unwrap();
+        assert_ne!(id_a, id_bar);
+
+        // After a reset, "foo" is a new generation and may be assigned a different id; in
+        // particular the thread-local front cache must not keep serving the stale one. Asking for
+        // "bar" first makes the fresh ids differ from the stale ones (foo: 0, bar: 1).
+        reset_string_cache();
+        let bar_after_reset = STRING_CACHE.get_cat("bar").unwrap();
+        assert_eq!(bar_after_reset, 0);
+        let id_after_reset = STRING_CACHE.get_cat("foo").unwrap();
+        assert_eq!(id_after_reset, 1);
+
+        reset_string_cache();
+    }
+
+    #[test]
+    fn intern_dedups_to_the_same_allocation() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        let a = intern("shared").unwrap();
+        let b = intern("shared").unwrap();
+        assert_eq!(a, b);
+        assert!(Arc::ptr_eq(&a.data, &b.data));
+
+        let other = intern("different").unwrap();
+        assert_ne!(a, other);
+
+        reset_string_cache();
+    }
+
+    #[test]
+    fn intern_survives_a_cache_reset() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        let handle = intern("outlives the generation").unwrap();
+        reset_string_cache();
+
+        // The handle owns its allocation via `Arc`, so it must still deref correctly even though
+        // the generation it was interned against is gone.
+        assert_eq!(&*handle, "outlives the generation");
+    }
+
+    #[test]
+    fn intern_compares_equal_across_a_generation_even_without_a_shared_allocation() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        let before = intern("reinterned").unwrap();
+        reset_string_cache();
+        let after = intern("reinterned").unwrap();
+
+        // Two different generations never share an allocation for the same string, so this can
+        // only pass via the byte-compare fallback, not `Arc::ptr_eq`.
+        assert!(!Arc::ptr_eq(&before.data, &after.data));
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn string_cache_size_tracks_inserts() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+        set_string_cache_limit(usize::MAX);
+
+        STRING_CACHE.get_cat("one").unwrap();
+        STRING_CACHE.get_cat("two").unwrap();
+        let (entries, bytes) = string_cache_size();
+        assert_eq!(entries, 2);
+        assert!(bytes > 0);
+
+        reset_string_cache();
+    }
+
+    #[test]
+    fn limit_smaller_than_a_single_entry_errors_instead_of_thrashing() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+        set_string_cache_limit(1);
+
+        let uuid_before = STRING_CACHE.read_map().uuid;
+        assert!(STRING_CACHE.get_cat("too big to ever fit under a 1-byte limit").is_err());
+        let uuid_after = STRING_CACHE.read_map().uuid;
+        // An unsatisfiable insert must be rejected, not evict the generation in a doomed retry.
+        assert_eq!(uuid_before, uuid_after);
+
+        set_string_cache_limit(usize::MAX);
+        reset_string_cache();
+    }
+
+    #[test]
+    fn export_import_round_trip_adopts_uuid_and_ids_into_an_empty_cache() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        STRING_CACHE.get_cat("a").unwrap();
+        STRING_CACHE.get_cat("b").unwrap();
+        let dict = export_string_cache();
+
+        reset_string_cache();
+        import_string_cache(&dict).unwrap();
+
+        assert!(is_current_generation(dict.uuid));
+        assert_eq!(STRING_CACHE.get_cat("a").unwrap(), 0);
+        assert_eq!(STRING_CACHE.get_cat("b").unwrap(), 1);
+
+        // A subsequently inserted category must not collide with an id the import already
+        // claimed.
+        let new_id = STRING_CACHE.get_cat("c").unwrap();
+        assert!(!dict.categories.iter().any(|(id, _)| *id == new_id));
+
+        reset_string_cache();
+    }
+
+    #[test]
+    fn import_into_a_nonempty_cache_keeps_the_local_generation() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        STRING_CACHE.get_cat("a").unwrap();
+        let local_uuid = STRING_CACHE.read_map().uuid;
+
+        // A producer dictionary that doesn't conflict with anything already local.
+        let dict = CategoryDictionary {
+            categories: vec![(5, "from-producer".to_string())],
+            uuid: local_uuid.wrapping_add(1),
+        };
+        import_string_cache(&dict).unwrap();
+
+        // Merging into an already-populated cache must not invalidate categoricals built against
+        // the local generation before the merge.
+        assert!(is_current_generation(local_uuid));
+        assert_eq!(STRING_CACHE.get_cat("from-producer").unwrap(), 5);
+
+        reset_string_cache();
+    }
+
+    #[test]
+    fn import_rejects_conflicting_assignment() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_string_cache();
+
+        STRING_CACHE.get_cat("a").unwrap();
+        let conflicting = CategoryDictionary {
+            categories: vec![(0, "not-a".to_string())],
+            uuid: 0,
+        };
+
+        assert!(import_string_cache(&conflicting).is_err());
+
+        reset_string_cache();
+    }
+}